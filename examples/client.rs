@@ -104,8 +104,8 @@ fn main() {
 
     {
         let stdin = io::stdin();
+        set_non_blocking(&stdin).unwrap();
         let fd = stdin.as_raw_fd();
-        set_non_blocking(fd).unwrap();
         let event = Event::new(
             Flags::EPOLLIN | Flags::EPOLLET,
             Data::new_box(Kind::Stdin(stdin)),
@@ -140,24 +140,18 @@ fn main() {
                     if flags.contains(Flags::EPOLLOUT) {
                         match server.write_buffer() {
                             State::WouldBlock(_) => {
-                                if !server.flags.contains(Flags::EPOLLOUT) {
-                                    info!("Register for write");
-                                    let flags = server.flags | Flags::EPOLLOUT;
-                                    wait.api
-                                        .mod_flags(server.stream.as_raw_fd(), flags)
-                                        .unwrap();
-                                    server.flags = flags;
-                                }
+                                info!("Register for write");
+                                server.flags = wait
+                                    .api
+                                    .add_interest(server.stream.as_raw_fd(), server.flags, Flags::EPOLLOUT)
+                                    .unwrap();
                             }
                             State::EndOfFile(_) => {
-                                if server.flags.contains(Flags::EPOLLOUT) {
-                                    info!("Unregister for write");
-                                    let flags = server.flags ^ Flags::EPOLLOUT;
-                                    wait.api
-                                        .mod_flags(server.stream.as_raw_fd(), flags)
-                                        .unwrap();
-                                    server.flags = flags;
-                                }
+                                info!("Unregister for write");
+                                server.flags = wait
+                                    .api
+                                    .clear_interest(server.stream.as_raw_fd(), server.flags, Flags::EPOLLOUT)
+                                    .unwrap();
                             }
                             State::Error(e) => {
                                 error!("{}", e);
@@ -177,13 +171,11 @@ fn main() {
                         match read_until_wouldblock(stdin, &mut server.buf_write, 4096) {
                             State::EndOfFile(_) => match server.write_buffer() {
                                 State::WouldBlock(_) => {
-                                    if !server.flags.contains(Flags::EPOLLOUT) {
-                                        info!("Register for write");
-                                        let fd = server.stream.as_raw_fd();
-                                        server.flags = server.flags | Flags::EPOLLOUT;
-                                        let flags = server.flags;
-                                        wait.api.mod_flags(fd, flags).unwrap();
-                                    }
+                                    info!("Register for write");
+                                    server.flags = wait
+                                        .api
+                                        .add_interest(server.stream.as_raw_fd(), server.flags, Flags::EPOLLOUT)
+                                        .unwrap();
                                     break 'run;
                                 }
                                 State::EndOfFile(_) => {
@@ -196,13 +188,11 @@ fn main() {
                             },
                             State::WouldBlock(_) => match server.write_buffer() {
                                 State::WouldBlock(_) => {
-                                    if !server.flags.contains(Flags::EPOLLOUT) {
-                                        info!("Register for write");
-                                        let fd = server.stream.as_raw_fd();
-                                        server.flags = server.flags | Flags::EPOLLOUT;
-                                        let flags = server.flags;
-                                        wait.api.mod_flags(fd, flags).unwrap();
-                                    }
+                                    info!("Register for write");
+                                    server.flags = wait
+                                        .api
+                                        .add_interest(server.stream.as_raw_fd(), server.flags, Flags::EPOLLOUT)
+                                        .unwrap();
                                 }
                                 State::EndOfFile(_) => {}
                                 State::Error(e) => {
@@ -37,12 +37,9 @@ impl Client {
                 Ok(n) => n,
                 Err(e) => {
                     if e.kind() == ErrorKind::WouldBlock {
-                        if !self.flags.contains(Flags::EPOLLOUT) {
-                            info!("Register for write");
-                            let flags = self.flags | Flags::EPOLLOUT;
-                            api.mod_flags(self.stream.as_raw_fd(), flags)?;
-                            self.flags = flags;
-                        }
+                        info!("Register for write");
+                        self.flags =
+                            api.add_interest(self.stream.as_raw_fd(), self.flags, Flags::EPOLLOUT)?;
                         return Ok(());
                     } else {
                         return Err(e);
@@ -53,12 +50,8 @@ impl Client {
             self.buffer.drain(..n);
         }
 
-        if self.flags.contains(Flags::EPOLLOUT) {
-            info!("Unregister for write");
-            let flags = self.flags ^ Flags::EPOLLOUT;
-            api.mod_flags(self.stream.as_raw_fd(), flags)?;
-            self.flags = flags;
-        }
+        info!("Unregister for write");
+        self.flags = api.clear_interest(self.stream.as_raw_fd(), self.flags, Flags::EPOLLOUT)?;
 
         Ok(())
     }
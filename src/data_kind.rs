@@ -3,7 +3,7 @@ use std::{
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    os::unix::io::{AsRawFd, RawFd},
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, OwnedFd, RawFd},
     rc::Rc,
     sync::Arc,
 };
@@ -88,11 +88,35 @@ impl Data<DataFd> {
         }
     }
 
+    /// Builds a `Data<DataFd>` from a borrowed fd, checked at compile time to
+    /// outlive this call. The epoll `data` union can only ever hold the raw
+    /// integer, so only the number is kept here; it is up to the caller that
+    /// the fd stays open for as long as it remains registered.
+    pub fn new_borrowed_fd(fd: BorrowedFd<'_>) -> Self {
+        Self::new_fd(fd.as_raw_fd())
+    }
+
+    /// Builds a `Data<DataFd>` from an owned fd, transferring ownership to
+    /// the caller's bookkeeping (typically the `EPoll` instance this is
+    /// registered into). The fd is converted to its raw form immediately
+    /// since the union can't carry the `OwnedFd` itself; like `DataPtr`/
+    /// `DataBox`, you are responsible for closing it, e.g. by recreating an
+    /// `OwnedFd` with `FromRawFd::from_raw_fd` when you're done with it.
+    pub fn new_owned_fd(fd: OwnedFd) -> Self {
+        Self::new_fd(fd.into_raw_fd())
+    }
+
     pub fn fd(&self) -> RawFd {
         unsafe { self.raw().fd }
     }
 }
 
+impl AsFd for Data<DataFd> {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd()) }
+    }
+}
+
 impl AsRawFd for Data<DataFd> {
     fn as_raw_fd(&self) -> RawFd {
         self.fd()
@@ -169,6 +193,18 @@ impl Data<DataU64> {
     pub fn _u64(&self) -> u64 {
         unsafe { self.raw._u64 }
     }
+
+    /// Alias for [`new_u64`](Self::new_u64) for callers using `Data<DataU64>`
+    /// as a mio-style `Token`: a plain integer key into a user-owned `Slab`
+    /// rather than a boxed pointer, avoiding an allocation per registration.
+    pub fn token(token: u64) -> Self {
+        Self::new_u64(token)
+    }
+
+    /// Alias for [`_u64`](Self::_u64) matching [`token`](Self::token).
+    pub fn token_value(&self) -> u64 {
+        self._u64()
+    }
 }
 
 impl Clone for Data<DataU64> {
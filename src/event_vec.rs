@@ -0,0 +1,122 @@
+use std::mem::MaybeUninit;
+
+use crate::data_kind::DataKind;
+use crate::{Event, MaxEvents};
+
+/// A caller-owned, reusable buffer for [`EPoll::wait_into`](crate::EPoll::wait_into).
+///
+/// `EPoll::wait` allocates its buffer once at construction and keeps it for
+/// the lifetime of the instance, but each `EPoll` only has one such buffer.
+/// `EventVec` lets a long-running reactor own as many reusable buffers as it
+/// needs (e.g. one per worker thread) and pass them into `wait_into`,
+/// avoiding any extra allocation across millions of `wait` calls.
+pub struct EventVec<T: DataKind> {
+    buffer: Vec<MaybeUninit<Event<T>>>,
+}
+
+impl<T: DataKind> EventVec<T> {
+    pub fn new<N: Into<MaxEvents>>(max_events: N) -> Self {
+        let max_events: MaxEvents = max_events.into();
+        let max_events: usize = max_events.into();
+
+        Self {
+            buffer: Vec::with_capacity(max_events),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut MaybeUninit<Event<T>> {
+        self.buffer.as_mut_ptr()
+    }
+
+    /// # Safety
+    ///
+    /// only safe if the first `len` elements of the buffer are initialized,
+    /// e.g. right after a successful `epoll_wait` reported `len` events.
+    pub(crate) unsafe fn set_len(
+        &mut self,
+        len: usize,
+    ) {
+        self.buffer.set_len(len);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Event<T>> {
+        self.as_slice().iter()
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = Event<T>> + '_ {
+        self.buffer.drain(..).map(|event| unsafe { event.assume_init() })
+    }
+
+    fn as_slice(&self) -> &[Event<T>] {
+        // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#method.slice_assume_init_ref
+        unsafe { &*(&self.buffer[..] as *const _ as *const [Event<T>]) }
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [Event<T>] {
+        // https://doc.rust-lang.org/std/mem/union.MaybeUninit.html#method.slice_assume_init_ref
+        unsafe { &mut *(&mut self.buffer[..] as *mut _ as *mut [Event<T>]) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EventVec;
+    use crate::data_kind::{Data, DataU32};
+    use crate::Event;
+    use crate::Flags;
+
+    #[test]
+    fn set_len_exposes_written_events() {
+        let mut events: EventVec<DataU32> = EventVec::new(4usize);
+
+        unsafe {
+            events
+                .as_mut_ptr()
+                .write(std::mem::MaybeUninit::new(Event::new(Flags::EPOLLIN, Data::new_u32(1))));
+            events
+                .as_mut_ptr()
+                .add(1)
+                .write(std::mem::MaybeUninit::new(Event::new(Flags::EPOLLOUT, Data::new_u32(2))));
+            events.set_len(2);
+        }
+
+        assert_eq!(events.len(), 2);
+        assert!(!events.is_empty());
+
+        let collected: Vec<u32> = events.iter().map(|event| event.data()._u32()).collect();
+        assert_eq!(collected, vec![1, 2]);
+
+        let drained: Vec<u32> = events.drain().map(|event| event.data()._u32()).collect();
+        assert_eq!(drained, vec![1, 2]);
+        assert_eq!(events.len(), 0);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn as_mut_slice_matches_len() {
+        let mut events: EventVec<DataU32> = EventVec::new(2usize);
+
+        unsafe {
+            events
+                .as_mut_ptr()
+                .write(std::mem::MaybeUninit::new(Event::new(Flags::EPOLLIN, Data::new_u32(42))));
+            events.set_len(1);
+        }
+
+        let slice = events.as_mut_slice();
+        assert_eq!(slice.len(), 1);
+        assert_eq!(slice[0].data()._u32(), 42);
+    }
+}
@@ -1,13 +1,30 @@
 pub mod data_kind;
 
-mod time_out;
-pub use time_out::TimeOut;
+mod timeout;
+pub use timeout::TimeOut;
 
 mod max_events;
 pub use max_events::MaxEvents;
 
+mod event_vec;
+pub use event_vec::EventVec;
+
 pub mod utils;
 
+pub mod reactor;
+
+mod waker;
+pub use waker::Waker;
+
+mod registration;
+pub use registration::Registration;
+
+mod timer_fd;
+pub use timer_fd::TimerFd;
+
+mod signal_fd;
+pub use signal_fd::SignalFd;
+
 use epoll::ControlOptions;
 pub use epoll::Events as Flags;
 
@@ -128,6 +145,7 @@ where
 pub struct EPoll<T: DataKind> {
     api: Api<T>,
     buffer: Vec<MaybeUninit<Event<T>>>,
+    waker: Option<Waker>,
 }
 
 impl<T: DataKind> AsRawFd for EPoll<T> {
@@ -159,7 +177,23 @@ impl<T> EPoll<DataBox<T>> {
 }
 
 pub trait EPollApi<T: DataKind> {
-    /// Safe wrapper to add an event for `libc::epoll_ctl`
+    /// Safe wrapper to add an event for `libc::epoll_ctl`.
+    ///
+    /// `fd` is taken as a bare `AsRawFd`, same as every other registration
+    /// entry point on this trait (`register`, `mod_flags`, ...): the caller
+    /// is responsible for keeping `fd` open for as long as it stays in
+    /// [`get_datas`](Self::get_datas), exactly like the raw `epoll_ctl` this
+    /// wraps.
+    ///
+    /// A `BorrowedFd`-based signature (plus an owned `dup` of `fd` stored
+    /// alongside the `Data<T>`) was tried here and reverted: every real
+    /// caller (the reactor, both examples) still had to reach for
+    /// `unsafe { BorrowedFd::borrow_raw(raw_fd) }` because the fd's owner is
+    /// either a local that's about to move into `event`'s `Data`, or one
+    /// already tracked elsewhere, so the signature bought no actual safety
+    /// and cost an extra `dup()` syscall per registration, while `register`
+    /// was left on `AsRawFd` the whole time anyway. Staying on `AsRawFd`
+    /// everywhere is the deliberate outcome, not an oversight.
     fn add<Fd: AsRawFd>(
         &mut self,
         fd: Fd,
@@ -179,10 +213,75 @@ pub trait EPollApi<T: DataKind> {
     /// you will need to use `ctl_mod()`
     fn get_datas(&self) -> &HashMap<RawFd, Data<T>>;
 
+    /// Adds `fd` to the interest set, like [`add`](Self::add), but returns a
+    /// [`Registration`] guard that removes it again on drop instead of
+    /// storing it in [`get_datas`](Self::get_datas). See `Registration` for
+    /// the tradeoff.
+    fn register<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        event: Event<T>,
+    ) -> io::Result<Registration<T>>;
+
     fn get_data_mut<Fd: AsRawFd>(
         &mut self,
         fd: Fd,
     ) -> Option<&mut Data<T>>;
+
+    /// Re-enables interest on a descriptor that fired under
+    /// `Flags::EPOLLONESHOT`. A one-shot registration is disarmed after it
+    /// fires, so this is just `mod_flags` under a name that says why you're
+    /// calling it again.
+    fn rearm<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        flags: Flags,
+    ) -> io::Result<()> {
+        self.mod_flags(fd, flags)
+    }
+
+    /// Sets interest to exactly `flags`, calling `mod_flags` only if that
+    /// differs from `current`. Returns the flags now in effect.
+    ///
+    /// This removes the repeated "if the flags changed, call mod_flags"
+    /// boilerplate a hand-rolled `EPOLLONESHOT` or edge-triggered reactor
+    /// otherwise needs; `current` is whatever the caller last observed this
+    /// registration's flags to be.
+    fn set_interest<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        current: Flags,
+        flags: Flags,
+    ) -> io::Result<Flags> {
+        if flags != current {
+            self.mod_flags(fd, flags)?;
+        }
+
+        Ok(flags)
+    }
+
+    /// Adds `flags` to `current`'s interest set, calling `mod_flags` only if
+    /// that actually changes anything. Returns the flags now in effect.
+    fn add_interest<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        current: Flags,
+        flags: Flags,
+    ) -> io::Result<Flags> {
+        self.set_interest(fd, current, current | flags)
+    }
+
+    /// Removes `flags` from `current`'s interest set, calling `mod_flags`
+    /// only if that actually changes anything. Returns the flags now in
+    /// effect.
+    fn clear_interest<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        current: Flags,
+        flags: Flags,
+    ) -> io::Result<Flags> {
+        self.set_interest(fd, current, current & !flags)
+    }
 }
 
 pub struct Wait<'a, T: DataKind> {
@@ -298,6 +397,25 @@ impl<T: DataKind> EPollApi<T> for Api<T> {
 
         self.datas.get_mut(&fd)
     }
+
+    #[instrument(skip(self, fd, event), level = "trace")]
+    fn register<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        mut event: Event<T>,
+    ) -> io::Result<Registration<T>> {
+        let fd = fd.as_raw_fd();
+        info!(self.fd, fd, flags = ?event.flags());
+
+        let op = ControlOptions::EPOLL_CTL_ADD as i32;
+        let event_ptr = &mut event as *mut _ as *mut libc::epoll_event;
+
+        if unsafe { libc::epoll_ctl(self.fd, op, fd, event_ptr) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Registration::new(self.fd, fd, event.into_data()))
+        }
+    }
 }
 
 impl<T: DataKind> EPollApi<T> for EPoll<T> {
@@ -327,6 +445,14 @@ impl<T: DataKind> EPollApi<T> for EPoll<T> {
     ) -> Option<&mut Data<T>> {
         self.api.get_data_mut(fd)
     }
+
+    fn register<Fd: AsRawFd>(
+        &mut self,
+        fd: Fd,
+        event: Event<T>,
+    ) -> io::Result<Registration<T>> {
+        self.api.register(fd, event)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -376,6 +502,7 @@ impl<T: DataKind> EPoll<T> {
             Ok(Self {
                 api: Api::new(ret),
                 buffer: Vec::with_capacity(max_events),
+                waker: None,
             })
         }
     }
@@ -493,6 +620,84 @@ impl<T: DataKind> EPoll<T> {
         }
     }
 
+    /// Like [`wait`](Self::wait), but backed by `libc::epoll_pwait`:
+    /// `sigmask` is atomically swapped in as the thread's signal mask for
+    /// the duration of the call, closing the race a plain `sigprocmask` +
+    /// `wait()` pair would have between unblocking a signal and actually
+    /// blocking in the syscall. Buffer handling and `Wait` construction are
+    /// otherwise identical to `wait()`.
+    ///
+    /// Pair this with [`SignalFd`] to have specific signals show up as
+    /// regular readable events in the same loop instead of interrupting it.
+    #[instrument(skip(self, time_out, sigmask), level = "trace")]
+    pub fn wait_with_sigmask<N: Into<TimeOut>>(
+        &mut self,
+        time_out: N,
+        sigmask: &libc::sigset_t,
+    ) -> Result<Wait<T>, Error> {
+        let time_out = time_out.into();
+        info!(self.api.fd, ?time_out);
+        let time_out = time_out.into();
+
+        unsafe {
+            let ret = libc::epoll_pwait(
+                self.as_raw_fd(),
+                self.buffer.as_mut_ptr() as *mut libc::epoll_event,
+                self.buffer.capacity() as libc::c_int,
+                time_out,
+                sigmask,
+            );
+
+            if ret < 0 {
+                Err(ret.into())
+            } else {
+                let num_events = ret as usize;
+                self.buffer.set_len(num_events);
+
+                let buffer = &mut *(self.buffer.as_mut_slice() as *mut _ as *mut [Event<T>]);
+
+                let wait = Wait::new(&mut self.api, buffer);
+                Ok(wait)
+            }
+        }
+    }
+
+    /// Safe wrapper for `libc::epoll_wait` filling a caller-owned [`EventVec`]
+    /// instead of this instance's own buffer.
+    ///
+    /// This lets a long-running reactor reuse a single `EventVec` allocation
+    /// across millions of `wait` calls. See [`wait`](Self::wait) for the
+    /// meaning of `time_out`.
+    #[instrument(skip(self, events, time_out), level = "trace")]
+    pub fn wait_into<'a, N: Into<TimeOut>>(
+        &'a mut self,
+        events: &'a mut EventVec<T>,
+        time_out: N,
+    ) -> Result<Wait<'a, T>, Error> {
+        let time_out = time_out.into();
+        info!(self.api.fd, ?time_out);
+        let time_out = time_out.into();
+
+        unsafe {
+            let ret = libc::epoll_wait(
+                self.as_raw_fd(),
+                events.as_mut_ptr() as *mut libc::epoll_event,
+                events.capacity() as libc::c_int,
+                time_out,
+            );
+
+            if ret < 0 {
+                Err(ret.into())
+            } else {
+                let num_events = ret as usize;
+                events.set_len(num_events);
+
+                let wait = Wait::new(&mut self.api, events.as_mut_slice());
+                Ok(wait)
+            }
+        }
+    }
+
     /// This resize the buffer used to recieve event
     #[instrument(skip(self, max_events), level = "trace")]
     pub fn resize_buffer<N: Into<MaxEvents>>(
@@ -508,6 +713,65 @@ impl<T: DataKind> EPoll<T> {
     }
 }
 
+impl EPoll<DataFd> {
+    /// Creates (on first call) an internal eventfd and registers it with
+    /// this instance's own interest set, returning a cheap, cloneable
+    /// [`Waker`] that other threads can use to interrupt a blocking
+    /// `wait()`. Subsequent calls return a clone of the same `Waker`.
+    ///
+    /// Pair this with [`wait_filtering_waker`](Self::wait_filtering_waker),
+    /// which drains and filters the waker's own readiness out of the
+    /// returned events so callers never see it.
+    #[instrument(skip(self), level = "trace")]
+    pub fn waker(&mut self) -> io::Result<Waker> {
+        if let Some(waker) = &self.waker {
+            return Ok(waker.clone());
+        }
+
+        let waker = Waker::new()?;
+        let event = Event::new(
+            Flags::EPOLLIN | Flags::EPOLLET,
+            Data::new_fd(waker.as_raw_fd()),
+        );
+        self.add(waker.as_raw_fd(), event)?;
+        self.waker = Some(waker.clone());
+
+        Ok(waker)
+    }
+
+    /// Like [`wait`](Self::wait), but drains and filters out the event for
+    /// the [`Waker`] created by [`waker`](Self::waker), if any, so it makes
+    /// a blocked `wait()` return without being surfaced as a regular event.
+    #[instrument(skip(self, time_out), level = "trace")]
+    pub fn wait_filtering_waker<N: Into<TimeOut>>(
+        &mut self,
+        time_out: N,
+    ) -> Result<Wait<DataFd>, Error> {
+        let waker = self.waker.clone();
+        let mut wait = self.wait(time_out)?;
+
+        if let Some(waker) = waker {
+            let fd = waker.as_raw_fd();
+            let mut len = wait.events.len();
+            let mut i = 0;
+
+            while i < len {
+                if wait.events[i].data().fd() == fd {
+                    let _ = waker.drain();
+                    wait.events.swap(i, len - 1);
+                    len -= 1;
+                } else {
+                    i += 1;
+                }
+            }
+
+            wait.events = &mut wait.events[..len];
+        }
+
+        Ok(wait)
+    }
+}
+
 #[cfg(test)]
 mod tests_epoll {
     use crate::*;
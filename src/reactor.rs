@@ -0,0 +1,185 @@
+//! A small `Future`-driving engine on top of [`EPoll`], the way async-io and
+//! smol drive their reactors over a raw epoll instance.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::poll_fn,
+    io::{self, Read},
+    os::unix::io::AsRawFd,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    data_kind::{Data, DataU64},
+    utils::{self, ReadSize, State},
+    EPoll, EPollApi, Event, Flags, MaxEvents, TimeOut,
+};
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Direction {
+    Read,
+    Write,
+}
+
+#[derive(Default)]
+struct Io {
+    read_ready: bool,
+    write_ready: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+/// Owns the single `EPoll` instance a whole `Async<T>` hierarchy registers
+/// into, plus the per-source read/write waker slots that [`drive`](Self::drive)
+/// fills on each `wait`.
+pub struct Reactor {
+    epoll: RefCell<EPoll<DataU64>>,
+    registry: RefCell<HashMap<u64, Io>>,
+    next_token: Cell<u64>,
+}
+
+impl Reactor {
+    pub fn new() -> io::Result<Rc<Self>> {
+        let epoll = EPoll::new(true, MaxEvents::DEFAULT)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        Ok(Rc::new(Self {
+            epoll: RefCell::new(epoll),
+            registry: RefCell::new(HashMap::new()),
+            next_token: Cell::new(0),
+        }))
+    }
+
+    fn next_token(&self) -> u64 {
+        let token = self.next_token.get();
+        self.next_token.set(token + 1);
+        token
+    }
+
+    fn poll_ready(
+        &self,
+        token: u64,
+        direction: Direction,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let mut registry = self.registry.borrow_mut();
+        let io = registry.get_mut(&token).expect("source not registered");
+
+        let (ready, waker) = match direction {
+            Direction::Read => (&mut io.read_ready, &mut io.read_waker),
+            Direction::Write => (&mut io.write_ready, &mut io.write_waker),
+        };
+
+        if *ready {
+            *ready = false;
+            Poll::Ready(Ok(()))
+        } else {
+            *waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Runs one `epoll_wait(time_out)` and wakes whichever tasks are parked
+    /// on the sources it reports ready.
+    pub fn drive<N: Into<TimeOut>>(
+        &self,
+        time_out: N,
+    ) -> io::Result<()> {
+        let mut epoll = self.epoll.borrow_mut();
+        let wait = epoll
+            .wait(time_out)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let mut registry = self.registry.borrow_mut();
+        for event in wait.events.iter() {
+            let token = event.data()._u64();
+            let flags = event.flags();
+
+            if let Some(io) = registry.get_mut(&token) {
+                if flags.intersects(Flags::EPOLLIN | Flags::EPOLLHUP | Flags::EPOLLERR) {
+                    io.read_ready = true;
+                    if let Some(waker) = io.read_waker.take() {
+                        waker.wake();
+                    }
+                }
+                if flags.intersects(Flags::EPOLLOUT | Flags::EPOLLHUP | Flags::EPOLLERR) {
+                    io.write_ready = true;
+                    if let Some(waker) = io.write_waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Registers `T`'s fd with the reactor under `EPOLLIN | EPOLLOUT | EPOLLET`
+/// and offers `readable()`/`writable()` futures that park the current task
+/// until [`Reactor::drive`] reports that direction ready.
+///
+/// Because registration is edge-triggered, callers should loop on the
+/// underlying fd (e.g. with [`utils::read_until_wouldblock`]) until it
+/// reports `WouldBlock` before awaiting `readable()`/`writable()` again.
+pub struct Async<T: AsRawFd> {
+    io: T,
+    reactor: Rc<Reactor>,
+    token: u64,
+}
+
+impl<T: AsRawFd> Async<T> {
+    pub fn new(
+        reactor: Rc<Reactor>,
+        io: T,
+    ) -> io::Result<Self> {
+        let token = reactor.next_token();
+        let flags = Flags::EPOLLIN | Flags::EPOLLOUT | Flags::EPOLLET;
+        let event = Event::new(flags, Data::new_u64(token));
+
+        reactor.epoll.borrow_mut().add(io.as_raw_fd(), event)?;
+        reactor.registry.borrow_mut().insert(token, Io::default());
+
+        Ok(Self { io, reactor, token })
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    pub async fn readable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.reactor.poll_ready(self.token, Direction::Read, cx)).await
+    }
+
+    pub async fn writable(&self) -> io::Result<()> {
+        poll_fn(|cx| self.reactor.poll_ready(self.token, Direction::Write, cx)).await
+    }
+}
+
+impl<T: AsRawFd + Read> Async<T> {
+    /// Awaits readability, then drains the fd with
+    /// [`utils::read_until_wouldblock`] until it reports `WouldBlock`, EOF,
+    /// or an error.
+    pub async fn read_until_wouldblock<S: Into<ReadSize>>(
+        &mut self,
+        output: &mut Vec<u8>,
+        read_size: S,
+    ) -> io::Result<State> {
+        self.readable().await?;
+
+        Ok(utils::read_until_wouldblock(&mut self.io, output, read_size))
+    }
+}
+
+impl<T: AsRawFd> Drop for Async<T> {
+    fn drop(&mut self) {
+        let _ = self.reactor.epoll.borrow_mut().del(self.io.as_raw_fd());
+        self.reactor.registry.borrow_mut().remove(&self.token);
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    net::{Shutdown, TcpStream},
+    os::unix::io::RawFd,
+    ptr::null_mut,
+};
+
+use epoll::ControlOptions;
+
+use crate::data_kind::{Data, DataKind, RawEvent};
+use crate::Flags;
+
+/// RAII guard for a registration made through
+/// [`EPollApi::register`](crate::EPollApi::register). Dropping it issues
+/// `EPOLL_CTL_DEL` on its target fd, echoing mio's "drop cancels interest"
+/// behavior so callers no longer need to track fds to delete by hand.
+///
+/// Because the guard owns its `Data<T>` independently, a source registered
+/// this way does not appear in [`EPollApi::get_datas`](crate::EPollApi::get_datas);
+/// use `register` for connections you want exception-safe teardown for, and
+/// the plain `add`/`del` pair for sources you need to look up through the
+/// `EPoll` instance itself.
+pub struct Registration<T: DataKind> {
+    pub(crate) epoll_fd: RawFd,
+    pub(crate) fd: RawFd,
+    pub(crate) data: Option<Data<T>>,
+    shutdown: Option<TcpStream>,
+}
+
+impl<T: DataKind> Registration<T> {
+    pub(crate) fn new(
+        epoll_fd: RawFd,
+        fd: RawFd,
+        data: Data<T>,
+    ) -> Self {
+        Self {
+            epoll_fd,
+            fd,
+            data: Some(data),
+            shutdown: None,
+        }
+    }
+
+    /// Modifies the interest set of this registration.
+    pub fn mod_flags(
+        &mut self,
+        flags: Flags,
+    ) -> std::io::Result<()> {
+        let data = self
+            .data
+            .as_ref()
+            .expect("Registration::mod_flags called after take_data")
+            .raw();
+
+        let mut raw_event = RawEvent {
+            flags: flags.bits(),
+            data,
+        };
+        let event = &mut raw_event as *mut _ as *mut libc::epoll_event;
+        let op = ControlOptions::EPOLL_CTL_MOD as i32;
+
+        if unsafe { libc::epoll_ctl(self.epoll_fd, op, self.fd, event) } < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Takes the `Data<T>` this registration was created with.
+    pub fn take_data(&mut self) -> Data<T> {
+        self.data
+            .take()
+            .expect("Registration::take_data called twice")
+    }
+
+    /// Opts this registration into also shutting down `stream` before
+    /// deregistering on drop, making connection teardown exception-safe.
+    pub fn shutdown_on_drop(
+        mut self,
+        stream: TcpStream,
+    ) -> Self {
+        self.shutdown = Some(stream);
+        self
+    }
+}
+
+impl<T: DataKind> Drop for Registration<T> {
+    fn drop(&mut self) {
+        if let Some(stream) = self.shutdown.take() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+
+        let op = ControlOptions::EPOLL_CTL_DEL as i32;
+        unsafe {
+            libc::epoll_ctl(self.epoll_fd, op, self.fd, null_mut());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TimeOut {
@@ -54,8 +56,53 @@ impl From<libc::c_int> for TimeOut {
     }
 }
 
+impl TimeOut {
+    /// Converts a `Duration` to a `TimeOut`, in milliseconds.
+    ///
+    /// A zero `Duration` maps to [`INSTANT`](Self::INSTANT). Any non-zero
+    /// duration with a sub-millisecond remainder rounds **up** to `1` ms so
+    /// callers never end up busy-spinning with an accidental `0` timeout.
+    /// Durations whose millisecond count exceeds `libc::c_int::MAX` saturate
+    /// to [`MAX`](Self::MAX) instead of overflowing.
+    pub fn from_duration(duration: Duration) -> Self {
+        if duration.is_zero() {
+            return Self::INSTANT;
+        }
+
+        let mut millis = duration.as_millis();
+        if duration.subsec_nanos() % 1_000_000 != 0 {
+            millis += 1;
+        }
+
+        if millis > Self::MAX.inner as u128 {
+            Self::MAX
+        } else {
+            Self::from(millis as libc::c_int)
+        }
+    }
+
+    /// The inverse of [`from_duration`](Self::from_duration).
+    /// [`INFINITE`](Self::INFINITE) has no finite `Duration` equivalent and
+    /// returns `None`.
+    pub fn as_duration(self) -> Option<Duration> {
+        if self == Self::INFINITE {
+            None
+        } else {
+            Some(Duration::from_millis(self.inner as u64))
+        }
+    }
+}
+
+impl From<Duration> for TimeOut {
+    fn from(duration: Duration) -> Self {
+        Self::from_duration(duration)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::TimeOut;
 
     fn timeout_new(timeout: libc::c_int) {
@@ -176,4 +223,63 @@ mod tests {
     fn into_default() {
         assert_eq!(Into::<libc::c_int>::into(TimeOut::DEFAULT), -1);
     }
+
+    #[test]
+    fn from_duration_zero() {
+        assert_eq!(TimeOut::from_duration(Duration::ZERO), TimeOut::INSTANT);
+    }
+
+    #[test]
+    fn from_duration_exact_millis() {
+        assert_eq!(
+            TimeOut::from_duration(Duration::from_millis(42)),
+            TimeOut::new(42).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_duration_rounds_up_sub_milli() {
+        assert_eq!(
+            TimeOut::from_duration(Duration::from_micros(1)),
+            TimeOut::new(1).unwrap()
+        );
+        assert_eq!(
+            TimeOut::from_duration(Duration::from_micros(1_500)),
+            TimeOut::new(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_duration_saturates_max() {
+        assert_eq!(
+            TimeOut::from_duration(Duration::from_secs(u64::MAX)),
+            TimeOut::MAX
+        );
+    }
+
+    #[test]
+    fn as_duration_infinite() {
+        assert_eq!(TimeOut::INFINITE.as_duration(), None);
+    }
+
+    #[test]
+    fn as_duration_instant() {
+        assert_eq!(TimeOut::INSTANT.as_duration(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn as_duration_round_trip() {
+        assert_eq!(
+            TimeOut::new(42).unwrap().as_duration(),
+            Some(Duration::from_millis(42))
+        );
+    }
+
+    #[test]
+    fn duration_into_timeout() {
+        assert_eq!(
+            TimeOut::from(Duration::from_millis(7)),
+            TimeOut::new(7).unwrap()
+        );
+    }
 }
@@ -0,0 +1,192 @@
+use std::{
+    io,
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd},
+    ptr::null_mut,
+    time::Duration,
+};
+
+/// A `timerfd`-backed timer that can be registered as a first-class epoll
+/// source (via `Flags::EPOLLIN`) instead of relying solely on `EPoll::wait`'s
+/// own timeout argument, the way async-io drives its timer wheel over a
+/// single reactor.
+pub struct TimerFd {
+    fd: RawFd,
+}
+
+impl TimerFd {
+    pub fn new() -> io::Result<Self> {
+        let fd =
+            unsafe { libc::timerfd_create(libc::CLOCK_MONOTONIC, libc::TFD_NONBLOCK | libc::TFD_CLOEXEC) };
+
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self { fd })
+        }
+    }
+
+    /// Arms the timer to fire once after `delay`.
+    pub fn set_once(
+        &self,
+        delay: Duration,
+    ) -> io::Result<()> {
+        self.set_time(delay, Duration::ZERO)
+    }
+
+    /// Arms the timer to fire every `interval`, starting after one interval.
+    pub fn set_interval(
+        &self,
+        interval: Duration,
+    ) -> io::Result<()> {
+        self.set_time(interval, interval)
+    }
+
+    /// Disarms the timer: it stops firing until `set_once`/`set_interval` is
+    /// called again.
+    pub fn disarm(&self) -> io::Result<()> {
+        self.set_time(Duration::ZERO, Duration::ZERO)
+    }
+
+    fn set_time(
+        &self,
+        initial: Duration,
+        interval: Duration,
+    ) -> io::Result<()> {
+        let new_value = libc::itimerspec {
+            it_interval: duration_to_timespec(interval),
+            it_value: duration_to_timespec(initial),
+        };
+
+        if unsafe { libc::timerfd_settime(self.fd, 0, &new_value, null_mut()) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Reads and drains the number of expirations since the last read, so a
+    /// periodic or level-triggered timer doesn't keep reporting readable.
+    pub fn read_expirations(&self) -> io::Result<u64> {
+        let mut value: u64 = 0;
+        let ret = unsafe { libc::read(self.fd, &mut value as *mut u64 as *mut libc::c_void, 8) };
+
+        if ret < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() == io::ErrorKind::WouldBlock {
+                Ok(0)
+            } else {
+                Err(e)
+            }
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Converts to `libc::timespec`, saturating `tv_sec` to `time_t::MAX` rather
+/// than silently wrapping when `duration` is too large to represent, the
+/// same overflow handling [`TimeOut::from_duration`](crate::TimeOut::from_duration)
+/// gives its own millisecond count.
+fn duration_to_timespec(duration: Duration) -> libc::timespec {
+    let tv_sec = duration
+        .as_secs()
+        .try_into()
+        .unwrap_or(libc::time_t::MAX);
+
+    libc::timespec {
+        tv_sec,
+        tv_nsec: duration.subsec_nanos() as libc::c_long,
+    }
+}
+
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl AsFd for TimerFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd) }
+    }
+}
+
+impl Drop for TimerFd {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_to_timespec, TimerFd};
+    use std::{thread::sleep, time::Duration};
+
+    /// Polls `read_expirations` until it reports at least one expiration or
+    /// `attempts` is exhausted, sleeping `step` between tries.
+    fn wait_for_expiration(
+        timer: &TimerFd,
+        step: Duration,
+        attempts: u32,
+    ) -> u64 {
+        for _ in 0..attempts {
+            let expirations = timer.read_expirations().unwrap();
+            if expirations > 0 {
+                return expirations;
+            }
+            sleep(step);
+        }
+
+        0
+    }
+
+    #[test]
+    fn read_expirations_is_zero_before_arming() {
+        let timer = TimerFd::new().unwrap();
+        assert_eq!(timer.read_expirations().unwrap(), 0);
+    }
+
+    #[test]
+    fn set_once_fires_a_single_expiration() {
+        let timer = TimerFd::new().unwrap();
+        timer.set_once(Duration::from_millis(10)).unwrap();
+
+        let expirations = wait_for_expiration(&timer, Duration::from_millis(5), 200);
+        assert_eq!(expirations, 1);
+    }
+
+    #[test]
+    fn disarm_prevents_expiration() {
+        let timer = TimerFd::new().unwrap();
+        timer.set_once(Duration::from_millis(10)).unwrap();
+        timer.disarm().unwrap();
+
+        let expirations = wait_for_expiration(&timer, Duration::from_millis(5), 20);
+        assert_eq!(expirations, 0);
+    }
+
+    #[test]
+    fn set_interval_fires_more_than_once() {
+        let timer = TimerFd::new().unwrap();
+        timer.set_interval(Duration::from_millis(5)).unwrap();
+
+        wait_for_expiration(&timer, Duration::from_millis(5), 200);
+        let second = wait_for_expiration(&timer, Duration::from_millis(5), 200);
+        assert!(second > 0);
+    }
+
+    #[test]
+    fn duration_to_timespec_saturates_tv_sec_on_overflow() {
+        let spec = duration_to_timespec(Duration::from_secs(u64::MAX));
+        assert_eq!(spec.tv_sec, libc::time_t::MAX);
+    }
+
+    #[test]
+    fn duration_to_timespec_keeps_small_values_exact() {
+        let spec = duration_to_timespec(Duration::new(42, 7));
+        assert_eq!(spec.tv_sec, 42);
+        assert_eq!(spec.tv_nsec, 7);
+    }
+}
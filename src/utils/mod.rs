@@ -1,8 +1,10 @@
 mod read_size;
 
 use std::{
-    io::{self, ErrorKind, Read},
-    os::unix::io::AsRawFd,
+    io::{self, ErrorKind, IoSliceMut, Read},
+    mem::MaybeUninit,
+    os::unix::io::{AsFd, AsRawFd, RawFd},
+    ptr::null_mut,
 };
 
 pub use read_size::ReadSize;
@@ -16,6 +18,51 @@ pub enum State {
     Error(io::Error),
 }
 
+/// A minimal, stable-Rust stand-in for `std::io::BorrowedCursor`: a window
+/// into a `Vec`'s spare capacity that tracks how much of it a `Read` call
+/// actually reported as filled, so that bookkeeping lives in one place
+/// instead of being re-derived at each call site.
+///
+/// `new` zero-fills `spare` up front, so by the time
+/// [`unfilled_mut`](Self::unfilled_mut) exposes it as `&mut [u8]`, every byte
+/// in the window is genuinely initialized (as zero) rather than relying on
+/// the `Read` impl never reading before writing. That costs a memset over
+/// up to `read_size` bytes per call, traded for the exposed slice being sound
+/// regardless of what `reader.read` does with it.
+struct ReadCursor<'a> {
+    spare: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+}
+
+impl<'a> ReadCursor<'a> {
+    fn new(spare: &'a mut [MaybeUninit<u8>]) -> Self {
+        for byte in spare.iter_mut() {
+            byte.write(0);
+        }
+
+        Self { spare, filled: 0 }
+    }
+
+    /// The zero-initialized tail, exposed as `&mut [u8]` for `Read::read` to
+    /// write into. Sound because `new` already initialized every byte in
+    /// `spare`.
+    fn unfilled_mut(&mut self) -> &mut [u8] {
+        unsafe { &mut *(self.spare as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Records that `read` reported `n` freshly-initialized bytes.
+    fn advance(
+        &mut self,
+        n: usize,
+    ) {
+        self.filled += n;
+    }
+
+    fn filled(&self) -> usize {
+        self.filled
+    }
+}
+
 /// This function assume the Read implementation don't do anything stupid sue me
 #[instrument(skip(reader, output, read_size), level = "trace")]
 pub fn read_until_wouldblock<R, S>(
@@ -33,27 +80,65 @@ where
 
     let mut total = 0;
     let ret = loop {
-        let available = output.capacity();
+        let available = output.capacity() - output.len();
         debug!(available);
         if available < read_size {
             let to_reserve = read_size - available;
             debug!(to_reserve);
             output.reserve(read_size - available);
         }
-        let buffer = unsafe {
-            std::slice::from_raw_parts_mut(output.as_mut_ptr().add(output.len()), read_size)
-        };
-        debug!(buffer = ?buffer.as_mut_ptr(), ptr = ?output.as_mut_ptr(), len = output.len(), cap = output.capacity(), read_size);
 
-        match reader.read(buffer) {
+        let ptr = output.as_mut_ptr();
+        let len = output.len();
+        let cap = output.capacity();
+        debug!(?ptr, len, cap, read_size);
+
+        let spare = output.spare_capacity_mut();
+        let window = read_size.min(spare.len());
+        let mut cursor = ReadCursor::new(&mut spare[..window]);
+
+        match reader.read(cursor.unfilled_mut()) {
             Ok(octet_read) => {
                 if octet_read == 0 {
                     break State::EndOfFile(total);
                 }
                 info!(octet_read);
                 total += octet_read;
+                cursor.advance(octet_read);
+                let filled = cursor.filled();
+
+                unsafe { output.set_len(output.len() + filled) }
+            }
+            Err(e) => {
+                break if e.kind() == ErrorKind::WouldBlock {
+                    State::WouldBlock(total)
+                } else {
+                    State::Error(e)
+                };
+            }
+        }
+    };
+
+    ret
+}
 
-                unsafe { output.set_len(output.len() + octet_read) }
+/// Vectored counterpart to [`read_until_wouldblock`].
+///
+/// Loops `read_vectored` over `bufs` until the reader reports `WouldBlock`,
+/// EOF, or another error, scattering into the caller's own pre-registered
+/// buffers instead of growing and memmove-ing a single contiguous `Vec<u8>`.
+#[instrument(skip(reader, bufs), level = "trace")]
+pub fn readv_until_wouldblock<R: Read>(
+    mut reader: R,
+    bufs: &mut [IoSliceMut<'_>],
+) -> State {
+    let mut total = 0;
+    let ret = loop {
+        match reader.read_vectored(bufs) {
+            Ok(0) => break State::EndOfFile(total),
+            Ok(octet_read) => {
+                info!(octet_read);
+                total += octet_read;
             }
             Err(e) => {
                 break if e.kind() == ErrorKind::WouldBlock {
@@ -68,8 +153,223 @@ where
     ret
 }
 
+/// An `O_NONBLOCK` pipe used as [`splice_until_wouldblock`]'s kernel-side
+/// staging area.
+///
+/// Create one per `src`/`dst` pair and keep passing the same instance to
+/// every `splice_until_wouldblock` call for that pair. A call can return
+/// with bytes already pulled from `src` still sitting unsplit-out to `dst`
+/// (e.g. `dst` was the one that would block); `pending` tracks that so the
+/// next call resumes draining from where the last one stopped instead of
+/// the bytes being abandoned in a freshly-created, freshly-closed pipe.
+pub struct Pipe {
+    read: RawFd,
+    write: RawFd,
+    pending: usize,
+}
+
+impl Pipe {
+    pub fn new() -> io::Result<Self> {
+        let mut fds = [0; 2];
+
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self {
+                read: fds[0],
+                write: fds[1],
+                pending: 0,
+            })
+        }
+    }
+}
+
+impl Drop for Pipe {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.read);
+            libc::close(self.write);
+        }
+    }
+}
+
+fn splice(
+    from: RawFd,
+    to: RawFd,
+    len: usize,
+) -> io::Result<usize> {
+    let ret = unsafe {
+        libc::splice(
+            from,
+            null_mut(),
+            to,
+            null_mut(),
+            len,
+            libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK | libc::SPLICE_F_MORE,
+        )
+    };
+
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret as usize)
+    }
+}
+
+/// Drains whatever is currently staged in `pipe` (tracked by `pipe.pending`)
+/// out to `dst`, adding every byte actually written to `total`. Returns
+/// `Some(state)` if draining stopped early (would-block or error) with
+/// `pipe.pending` left at however much is still stuck in the pipe for next
+/// time; returns `None` once the pipe is fully drained.
+fn drain_pipe(
+    pipe: &mut Pipe,
+    dst: RawFd,
+    total: &mut usize,
+) -> Option<State> {
+    while pipe.pending > 0 {
+        match splice(pipe.read, dst, pipe.pending) {
+            Ok(written) => {
+                pipe.pending -= written;
+                *total += written;
+            }
+            Err(e) => {
+                return Some(if e.kind() == ErrorKind::WouldBlock {
+                    State::WouldBlock(*total)
+                } else {
+                    State::Error(e)
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Moves bytes from `src` to `dst` entirely inside the kernel via
+/// `libc::splice`, through `pipe`'s kernel-side staging area, instead of
+/// round-tripping them through a userspace buffer the way
+/// [`read_until_wouldblock`] plus a `write` loop would. This is the same
+/// copy optimization std's `io::copy` uses on Linux, aimed at proxying
+/// between two already non-blocking, already-registered epoll sources.
+///
+/// `pipe` is caller-owned and must be reused across calls for the same
+/// `src`/`dst` pair: if `dst` would block partway through draining a chunk
+/// just pulled from `src`, the undrained remainder stays recorded in `pipe`
+/// rather than being lost, and the next call picks up draining it before
+/// pulling in anything new.
+///
+/// Loops `src -> pipe -> dst`, accumulating the total bytes moved, until
+/// `src` reports EOF or either side would block.
+#[instrument(skip(src, dst, pipe, chunk), level = "trace")]
+pub fn splice_until_wouldblock<Src, Dst, S>(
+    src: Src,
+    dst: Dst,
+    pipe: &mut Pipe,
+    chunk: S,
+) -> State
+where
+    Src: AsFd,
+    Dst: AsFd,
+    S: Into<ReadSize>,
+{
+    let src = src.as_fd().as_raw_fd();
+    let dst = dst.as_fd().as_raw_fd();
+    let chunk: usize = chunk.into().into();
+    info!(chunk);
+
+    let mut total = 0;
+
+    if let Some(state) = drain_pipe(pipe, dst, &mut total) {
+        return state;
+    }
+
+    loop {
+        let moved = match splice(src, pipe.write, chunk) {
+            Ok(0) => break State::EndOfFile(total),
+            Ok(moved) => moved,
+            Err(e) => {
+                break if e.kind() == ErrorKind::WouldBlock {
+                    State::WouldBlock(total)
+                } else {
+                    State::Error(e)
+                };
+            }
+        };
+        info!(moved);
+        pipe.pending = moved;
+
+        if let Some(state) = drain_pipe(pipe, dst, &mut total) {
+            return state;
+        }
+    }
+}
+
+/// Sets `O_NONBLOCK` on `fd`, preserving its other flags.
+///
+/// `fd` is taken as `impl AsFd` so the borrow is lifetime-checked at compile
+/// time, preventing this from being called on an fd that has already been
+/// closed. Callers stuck with raw fds can enable the "raw" feature and use
+/// [`set_non_blocking_raw`] instead.
+#[instrument(skip(fd), level = "trace")]
+pub fn set_non_blocking<Fd: AsFd>(fd: Fd) -> io::Result<()> {
+    let fd = fd.as_fd().as_raw_fd();
+    info!(fd);
+
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags == -1 {
+            Err(io::Error::last_os_error())
+        } else if flags & libc::O_NONBLOCK != libc::O_NONBLOCK {
+            if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Sets `O_NONBLOCK` on `fd` via a single `ioctl(fd, FIONBIO, &1)` call.
+///
+/// Unlike [`set_non_blocking`], which does a `fcntl` read-modify-write of
+/// the flag word, this toggles `O_NONBLOCK` alone in one syscall, so there's
+/// no race with another thread changing the other flags in between.
+#[instrument(skip(fd), level = "trace")]
+pub fn set_non_blocking_fionbio<Fd: AsFd>(fd: Fd) -> io::Result<()> {
+    fionbio(fd, true)
+}
+
+/// Clears `O_NONBLOCK` on `fd` via a single `ioctl(fd, FIONBIO, &0)` call.
+/// See [`set_non_blocking_fionbio`].
+#[instrument(skip(fd), level = "trace")]
+pub fn clear_non_blocking<Fd: AsFd>(fd: Fd) -> io::Result<()> {
+    fionbio(fd, false)
+}
+
+fn fionbio<Fd: AsFd>(
+    fd: Fd,
+    non_blocking: bool,
+) -> io::Result<()> {
+    let fd = fd.as_fd().as_raw_fd();
+    let value: libc::c_int = non_blocking as libc::c_int;
+    info!(fd, value);
+
+    if unsafe { libc::ioctl(fd, libc::FIONBIO, &value as *const libc::c_int) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Back-compat equivalent of [`set_non_blocking`] for callers who only have
+/// a raw fd and can't provide a borrow-checked `AsFd`. Gated behind the
+/// "raw" feature since it reopens the use-after-close hazard `AsFd` exists
+/// to prevent.
+#[cfg(feature = "raw")]
 #[instrument(skip(fd), level = "trace")]
-pub fn set_non_blocking<Fd: AsRawFd>(fd: Fd) -> io::Result<()> {
+pub fn set_non_blocking_raw<Fd: AsRawFd>(fd: Fd) -> io::Result<()> {
     let fd = fd.as_raw_fd();
     info!(fd);
 
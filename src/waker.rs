@@ -0,0 +1,121 @@
+use std::{
+    io,
+    os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+    sync::Arc,
+};
+
+/// A cheap, cloneable, `Send` handle that can unblock a thread parked in
+/// `EPoll::wait` from another thread, the way mio's `Waker` and async-io's
+/// notifier do.
+///
+/// Internally this is just an `eventfd`. Register `as_raw_fd()` with
+/// `Flags::EPOLLIN | Flags::EPOLLET` (e.g. via `EPollApi::add`); each
+/// `wake()` call writes `1` to it, which makes the pending `epoll_wait`
+/// return. Once woken, drain the counter with [`drain`](Self::drain) before
+/// the fd is polled again.
+#[derive(Clone)]
+pub struct Waker {
+    fd: Arc<OwnedFd>,
+}
+
+impl Waker {
+    pub fn new() -> io::Result<Self> {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Self {
+                fd: Arc::new(unsafe { OwnedFd::from_raw_fd(fd) }),
+            })
+        }
+    }
+
+    /// Wakes a thread parked in `epoll_wait` on this eventfd.
+    pub fn wake(&self) -> io::Result<()> {
+        let value: u64 = 1;
+        let ret =
+            unsafe { libc::write(self.fd.as_raw_fd(), &value as *const u64 as *const libc::c_void, 8) };
+
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Drains the eventfd counter, returning the accumulated wakeup count.
+    /// Call this after each wakeup so the fd stops reporting readable.
+    pub fn drain(&self) -> io::Result<u64> {
+        let mut value: u64 = 0;
+        let ret =
+            unsafe { libc::read(self.fd.as_raw_fd(), &mut value as *mut u64 as *mut libc::c_void, 8) };
+
+        if ret < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() == io::ErrorKind::WouldBlock {
+                Ok(0)
+            } else {
+                Err(e)
+            }
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+impl AsRawFd for Waker {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl AsFd for Waker {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Waker;
+
+    #[test]
+    fn drain_with_no_wake_returns_zero() {
+        let waker = Waker::new().unwrap();
+        assert_eq!(waker.drain().unwrap(), 0);
+    }
+
+    #[test]
+    fn wake_then_drain_round_trips() {
+        let waker = Waker::new().unwrap();
+        waker.wake().unwrap();
+        assert_eq!(waker.drain().unwrap(), 1);
+    }
+
+    #[test]
+    fn multiple_wakes_accumulate_before_drain() {
+        let waker = Waker::new().unwrap();
+        waker.wake().unwrap();
+        waker.wake().unwrap();
+        waker.wake().unwrap();
+        assert_eq!(waker.drain().unwrap(), 3);
+    }
+
+    #[test]
+    fn drain_resets_the_counter() {
+        let waker = Waker::new().unwrap();
+        waker.wake().unwrap();
+        assert_eq!(waker.drain().unwrap(), 1);
+        assert_eq!(waker.drain().unwrap(), 0);
+    }
+
+    #[test]
+    fn clone_shares_the_same_eventfd() {
+        let waker = Waker::new().unwrap();
+        let clone = waker.clone();
+
+        waker.wake().unwrap();
+        assert_eq!(clone.drain().unwrap(), 1);
+    }
+}